@@ -1,16 +1,68 @@
 extern crate num;
+extern crate rayon;
 
 #[cfg(test)]
 #[macro_use]
 extern crate quickcheck;
 
 use num::Float;
+use rayon::prelude::*;
 use std::cmp;
+use std::collections::VecDeque;
 use std::fmt;
+use std::io::{self, BufRead};
+use std::time::{Duration, Instant};
 
 /// Structure to represent a Travelling Salesman Problem.
 pub struct TSP<N: Float> {
     distances: Vec<Vec<N>>,
+    /// For each vertex, the other vertices sorted by increasing distance;
+    /// candidate lists for the neighbor-list accelerated local search.
+    neighbors: Vec<Vec<usize>>,
+    /// The original coordinates of each vertex, when the problem was built
+    /// from a coordinate list. Needed by the spatial clustering solver.
+    coords: Option<Vec<Vec<N>>>,
+}
+
+/// Parameters controlling a [`TSP::do_annealing`] run.
+pub struct AnnealConfig<N: Float> {
+    /// Wall-clock budget for the whole run.
+    pub budget: Duration,
+    /// Starting (hot) temperature.
+    pub t_start: N,
+    /// Final (cold) temperature.
+    pub t_end: N,
+    /// Seed for the internal pseudo-random number generator.
+    pub seed: u64,
+}
+
+/// A small, fast xorshift PRNG used by the stochastic solvers.
+struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    fn new(seed: u64) -> Self {
+        XorShift64 {
+            state: seed ^ 0x9e3779b97f4a7c15,
+        }
+    }
+    fn next_u64(&mut self) -> u64 {
+        let mut x = if self.state == 0 { 1 } else { self.state };
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+    /// Returns a value uniformly distributed in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+    /// Returns a value uniformly distributed in `0..bound`.
+    fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
 }
 
 impl<N: Float + fmt::Debug> TSP<N> {
@@ -38,13 +90,31 @@ impl<N: Float + fmt::Debug> TSP<N> {
             Self::exchange(path, a + bc, b, c);
         }
     }
+    /// Builds the sorted neighbor candidate lists from a distance matrix.
+    fn build_neighbors(distances: &[Vec<N>]) -> Vec<Vec<usize>> {
+        let n = distances.len();
+        let mut neighbors = Vec::with_capacity(n);
+        for i in 0..n {
+            let mut others: Vec<usize> = (0..n).filter(|&j| j != i).collect();
+            others.sort_by(|&a, &b| distances[i][a].partial_cmp(&distances[i][b]).unwrap());
+            neighbors.push(others);
+        }
+        neighbors
+    }
+    /// Assembles a problem from a distance matrix and optional coordinates,
+    /// precomputing the neighbor candidate lists.
+    fn assemble(distances: Vec<Vec<N>>, coords: Option<Vec<Vec<N>>>) -> Self {
+        let neighbors = Self::build_neighbors(&distances);
+        TSP { distances: distances, neighbors: neighbors, coords: coords }
+    }
     /// Creates a problem from a list of 2D real coordinates.
     pub fn new_euc2d(vertices: &[(N, N)]) -> Self {
         let mut distances = Vec::new();
         for a in vertices {
             distances.push(vertices.iter().map(|&b| TSP::euc2d(*a, b)).collect());
         }
-        TSP { distances: distances }
+        let coords = vertices.iter().map(|&(x, y)| vec![x, y]).collect();
+        Self::assemble(distances, Some(coords))
     }
     /// Creates a problem from a list of 3D real coordinates.
     pub fn new_euc3d(vertices: &[(N, N, N)]) -> Self {
@@ -52,7 +122,96 @@ impl<N: Float + fmt::Debug> TSP<N> {
         for a in vertices {
             distances.push(vertices.iter().map(|&b| TSP::euc3d(*a, b)).collect());
         }
-        TSP { distances: distances }
+        let coords = vertices.iter().map(|&(x, y, z)| vec![x, y, z]).collect();
+        Self::assemble(distances, Some(coords))
+    }
+    /// Returns the great-circle distance in kilometers between two
+    /// latitude/longitude points (in degrees) with the haversine formula.
+    fn haversine(a: (N, N), b: (N, N)) -> N {
+        let radius = N::from(6371.0).unwrap();
+        let two = N::from(2.0).unwrap();
+        let lat1 = a.0.to_radians();
+        let lat2 = b.0.to_radians();
+        let dlat = (b.0 - a.0).to_radians();
+        let dlon = (b.1 - a.1).to_radians();
+        let h = (dlat / two).sin() * (dlat / two).sin() +
+            lat1.cos() * lat2.cos() * (dlon / two).sin() * (dlon / two).sin();
+        two * radius * h.sqrt().asin()
+    }
+    /// Creates a problem directly from a precomputed distance matrix, which
+    /// may be asymmetric or non-metric.
+    pub fn from_matrix(distances: Vec<Vec<N>>) -> Self {
+        Self::assemble(distances, None)
+    }
+    /// Creates a problem from a list of geographic `(latitude, longitude)`
+    /// coordinates in degrees, using haversine great-circle distances.
+    pub fn new_geo(points: &[(N, N)]) -> Self {
+        let mut distances = Vec::new();
+        for a in points {
+            distances.push(points.iter().map(|&b| TSP::haversine(*a, b)).collect());
+        }
+        let coords = points.iter().map(|&(lat, lon)| vec![lat, lon]).collect();
+        Self::assemble(distances, Some(coords))
+    }
+    /// Loads a problem from a TSPLIB stream, parsing the `NODE_COORD_SECTION`
+    /// for the `EUC_2D` and `GEO` edge-weight types.
+    ///
+    /// `GEO` coordinates are given in the TSPLIB `DDD.MM` degrees-and-minutes
+    /// convention; they are converted to decimal degrees and handed to
+    /// [`TSP::new_geo`].
+    pub fn from_tsplib<R: BufRead>(reader: R) -> io::Result<Self> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+        let mut edge_weight_type = String::new();
+        let mut coords: Vec<(N, N)> = Vec::new();
+        let mut in_section = false;
+        for line in reader.lines() {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            if trimmed == "EOF" {
+                break;
+            }
+            if !in_section {
+                if trimmed == "NODE_COORD_SECTION" {
+                    in_section = true;
+                } else if let Some(pos) = trimmed.find(':') {
+                    let key = trimmed[..pos].trim();
+                    if key == "EDGE_WEIGHT_TYPE" {
+                        edge_weight_type = trimmed[pos + 1..].trim().to_string();
+                    }
+                }
+                continue;
+            }
+            let mut fields = trimmed.split_whitespace();
+            let _ = fields.next();
+            let x = fields
+                .next()
+                .and_then(|t| t.parse::<f64>().ok())
+                .ok_or_else(|| invalid("malformed coordinate line"))?;
+            let y = fields
+                .next()
+                .and_then(|t| t.parse::<f64>().ok())
+                .ok_or_else(|| invalid("malformed coordinate line"))?;
+            coords.push((N::from(x).unwrap(), N::from(y).unwrap()));
+        }
+        match edge_weight_type.as_str() {
+            "EUC_2D" => Ok(Self::new_euc2d(&coords)),
+            "GEO" => {
+                let to_degrees = |v: N| {
+                    let deg = v.trunc();
+                    deg + (v - deg) * N::from(5.0 / 3.0).unwrap()
+                };
+                let geo: Vec<(N, N)> = coords
+                    .iter()
+                    .map(|&(lat, lon)| (to_degrees(lat), to_degrees(lon)))
+                    .collect();
+                Ok(Self::new_geo(&geo))
+            }
+            "" => Err(invalid("missing EDGE_WEIGHT_TYPE")),
+            other => Err(invalid(&format!("unsupported EDGE_WEIGHT_TYPE: {}", other))),
+        }
     }
     /// Returns a 2-opt tour and its score, from an optionally specified
     /// starting tour.
@@ -170,6 +329,398 @@ impl<N: Float + fmt::Debug> TSP<N> {
         }
         (distance, path)
     }
+    /// Returns the optimal tour and its score, computed exactly with the
+    /// Held–Karp bitmask dynamic program.
+    ///
+    /// `dp[S][j]` is the minimum cost of a path that starts at vertex 0,
+    /// visits exactly the vertices in the bitmask `S` (which always contains
+    /// both 0 and `j`) and ends at `j`. Memory is `O(2^n · n)`, so this panics
+    /// for `n` above 20 rather than returning an error: the fixed
+    /// `(N, Vec<usize>)` signature leaves no room for a fallible result.
+    /// Use the local-search methods for larger instances.
+    pub fn solve_exact(&self) -> (N, Vec<usize>) {
+        let n = self.distances.len();
+        assert!(
+            n <= 20,
+            "solve_exact is limited to at most 20 vertices (got {})",
+            n
+        );
+        if n <= 1 {
+            return (N::zero(), (0..n).collect());
+        }
+        let full = 1usize << n;
+        let mut dp = vec![vec![N::infinity(); n]; full];
+        let mut parent = vec![vec![usize::max_value(); n]; full];
+        for j in 1..n {
+            dp[(1 << 0) | (1 << j)][j] = self.distances[0][j];
+            // Base states come straight from vertex 0; terminate the walk there.
+            parent[(1 << 0) | (1 << j)][j] = 0;
+        }
+        for s in 0..full {
+            if s & 1 == 0 {
+                continue;
+            }
+            for j in 1..n {
+                if s & (1 << j) == 0 {
+                    continue;
+                }
+                let prev = s ^ (1 << j);
+                for k in 1..n {
+                    if prev & (1 << k) == 0 {
+                        continue;
+                    }
+                    let candidate = dp[prev][k] + self.distances[k][j];
+                    if candidate < dp[s][j] {
+                        dp[s][j] = candidate;
+                        parent[s][j] = k;
+                    }
+                }
+            }
+        }
+        let mut best = N::infinity();
+        let mut last = 0;
+        for j in 1..n {
+            let candidate = dp[full - 1][j] + self.distances[j][0];
+            if candidate < best {
+                best = candidate;
+                last = j;
+            }
+        }
+        let mut path = Vec::with_capacity(n);
+        let mut s = full - 1;
+        let mut j = last;
+        while j != 0 {
+            path.push(j);
+            let p = parent[s][j];
+            s ^= 1 << j;
+            j = p;
+        }
+        path.push(0);
+        path.reverse();
+        (best, path)
+    }
+    /// Returns a tour and its score found by simulated annealing, run until
+    /// the `config.budget` wall-clock time elapses.
+    ///
+    /// Each iteration proposes a random 2-opt reversal, scores it with the
+    /// same four-edge delta as [`TSP::do_2opt`], and accepts it when it either
+    /// improves the tour or passes the Metropolis criterion
+    /// `rng < exp(-delta / T)`. The temperature cools geometrically from
+    /// `t_start` to `t_end` as the budget is spent, and the best tour ever
+    /// seen is returned.
+    pub fn do_annealing(&self, start: Option<&[usize]>, config: AnnealConfig<N>) -> (N, Vec<usize>) {
+        let mut path = match start {
+            None => (0..self.distances.len()).collect::<Vec<usize>>(),
+            Some(path) => path.to_vec(),
+        };
+        let n = path.len();
+        let distance = |path: &[usize]| {
+            let mut distance = N::zero();
+            for i in 0..path.len() {
+                distance = distance + self.distances[path[i]][path[(i + 1) % n]];
+            }
+            distance
+        };
+        if n < 4 {
+            let d = distance(&path);
+            return (d, path);
+        }
+        let mut rng = XorShift64::new(config.seed);
+        let mut current = distance(&path);
+        let mut best = current;
+        let mut best_path = path.clone();
+        let budget = config.budget.as_secs_f64();
+        let start_time = Instant::now();
+        loop {
+            let elapsed = start_time.elapsed().as_secs_f64();
+            if elapsed >= budget {
+                break;
+            }
+            let ratio = N::from(elapsed / budget).unwrap();
+            let temperature = config.t_start * (config.t_end / config.t_start).powf(ratio);
+            // Pick i < j, the move reverses path[i + 1..=j].
+            let i = rng.next_below(n - 2);
+            let j = i + 2 + rng.next_below(n - i - 2);
+            let delta = self.distances[path[i]][path[j]] +
+                self.distances[path[i + 1]][path[(j + 1) % n]] -
+                self.distances[path[i]][path[i + 1]] -
+                self.distances[path[j]][path[(j + 1) % n]];
+            let accept = delta < N::zero() ||
+                N::from(rng.next_f64()).unwrap() < (-delta / temperature).exp();
+            if accept {
+                path[i + 1..j + 1].reverse();
+                current = current + delta;
+                if current < best {
+                    best = current;
+                    best_path.copy_from_slice(&path);
+                }
+            }
+        }
+        (best, best_path)
+    }
+    /// Returns the best tour found over `restarts` independent local searches
+    /// started from randomized permutations, run in parallel with rayon.
+    ///
+    /// Both [`TSP::do_2opt`] and [`TSP::do_3opt`] are sensitive to their
+    /// starting tour, so running many randomized starts and keeping the
+    /// minimum substantially improves the result. The base vertex 0 is kept at
+    /// the front of every start to match the rest of the crate.
+    pub fn do_multistart(&self, restarts: usize, seed: u64) -> (N, Vec<usize>)
+    where
+        N: Send + Sync,
+    {
+        let n = self.distances.len();
+        (0..restarts)
+            .into_par_iter()
+            .map(|r| {
+                let mut rng = XorShift64::new(seed ^ (r as u64).wrapping_mul(0x2545f4914f6cdd1d));
+                let mut start = (0..n).collect::<Vec<usize>>();
+                // Fisher–Yates shuffle of 1..n, leaving vertex 0 in front.
+                for i in (2..n).rev() {
+                    let j = 1 + rng.next_below(i);
+                    start.swap(i, j);
+                }
+                let (_, path) = self.do_2opt(Some(&start));
+                self.do_3opt(Some(&path))
+            })
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+            .unwrap_or_else(|| self.do_2opt(None))
+    }
+    /// Returns a nearest-neighbor tour, a good warm start for the local-search
+    /// methods which otherwise begin from the identity permutation.
+    ///
+    /// Starting at vertex 0, the nearest not-yet-visited vertex is repeatedly
+    /// appended, ties being broken by the lower index.
+    pub fn greedy(&self) -> Vec<usize> {
+        let n = self.distances.len();
+        let mut path = Vec::with_capacity(n);
+        if n == 0 {
+            return path;
+        }
+        let mut visited = vec![false; n];
+        let mut current = 0;
+        visited[0] = true;
+        path.push(0);
+        for _ in 1..n {
+            let mut next = None;
+            for j in 0..n {
+                if visited[j] {
+                    continue;
+                }
+                match next {
+                    Some((_, best)) if self.distances[current][j] >= best => {}
+                    _ => next = Some((j, self.distances[current][j])),
+                }
+            }
+            let (j, _) = next.unwrap();
+            visited[j] = true;
+            path.push(j);
+            current = j;
+        }
+        path
+    }
+    /// Returns a 2-opt tour and its score, considering only moves that join a
+    /// vertex to one of its `k` nearest candidate neighbors.
+    ///
+    /// A don't-look bit is kept per vertex: active vertices sit in a queue,
+    /// each is scanned for an improving move over its candidate edges only,
+    /// and a vertex with no such move has its bit cleared until a later move
+    /// reactivates one of its endpoints. Each sweep is therefore roughly
+    /// `O(n · k)` instead of the `O(n²)` of [`TSP::do_2opt`], which lets the
+    /// crate handle thousands of cities.
+    pub fn do_2opt_nn(&self, k: usize, start: Option<&[usize]>) -> (N, Vec<usize>) {
+        let mut path = match start {
+            None => (0..self.distances.len()).collect::<Vec<usize>>(),
+            Some(path) => path.to_vec(),
+        };
+        let n = path.len();
+        if n < 4 {
+            let mut distance = N::zero();
+            for i in 0..n {
+                distance = distance + self.distances[path[i]][path[(i + 1) % n]];
+            }
+            return (distance, path);
+        }
+        let mut pos = vec![0usize; n];
+        for (i, &c) in path.iter().enumerate() {
+            pos[c] = i;
+        }
+        let mut active = vec![true; n];
+        let mut queue: VecDeque<usize> = path.iter().cloned().collect();
+        let epsilon = N::from(-1e-10).unwrap();
+        while let Some(c1) = queue.pop_front() {
+            if !active[c1] {
+                continue;
+            }
+            active[c1] = false;
+            let kk = cmp::min(k, self.neighbors[c1].len());
+            for idx in 0..kk {
+                let c2 = self.neighbors[c1][idx];
+                let (a, b) = if pos[c1] < pos[c2] {
+                    (pos[c1], pos[c2])
+                } else {
+                    (pos[c2], pos[c1])
+                };
+                if b <= a + 1 {
+                    continue;
+                }
+                let delta = self.distances[path[a]][path[b]] +
+                    self.distances[path[a + 1]][path[(b + 1) % n]] -
+                    self.distances[path[a]][path[a + 1]] -
+                    self.distances[path[b]][path[(b + 1) % n]];
+                if delta < epsilon {
+                    path[a + 1..b + 1].reverse();
+                    for i in a + 1..b + 1 {
+                        pos[path[i]] = i;
+                    }
+                    for &v in &[path[a], path[a + 1], path[b], path[(b + 1) % n]] {
+                        if !active[v] {
+                            active[v] = true;
+                            queue.push_back(v);
+                        }
+                    }
+                    if !active[c1] {
+                        active[c1] = true;
+                        queue.push_back(c1);
+                    }
+                    break;
+                }
+            }
+        }
+        let mut distance = N::zero();
+        for i in 0..n {
+            distance = distance + self.distances[path[i]][path[(i + 1) % n]];
+        }
+        (distance, path)
+    }
+    /// Returns a tour and its score built by spatial divide-and-conquer.
+    ///
+    /// Lloyd's k-means partitions the stored coordinates into `k` clusters,
+    /// each cluster's sub-tour is solved with [`TSP::do_2opt`], a tour over the
+    /// cluster centroids fixes the visiting order, and the sub-tours are
+    /// stitched together in that order by entering each cluster at its vertex
+    /// nearest the previous cluster's exit. This keeps very large instances
+    /// tractable. Falls back to a flat [`TSP::do_2opt`] when no coordinates are
+    /// stored or `k` is degenerate.
+    pub fn solve_clustered(&self, k: usize) -> (N, Vec<usize>) {
+        let n = self.distances.len();
+        let coords = match self.coords {
+            Some(ref c) => c,
+            None => return self.do_2opt(None),
+        };
+        if k <= 1 || k >= n {
+            return self.do_2opt(None);
+        }
+        let dim = coords[0].len();
+        let euclid = |a: &[N], b: &[N]| {
+            let mut s = N::zero();
+            for d in 0..dim {
+                s = s + (a[d] - b[d]) * (a[d] - b[d]);
+            }
+            s.sqrt()
+        };
+        // Lloyd's k-means, deterministically seeded from the first k points.
+        let mut centroids: Vec<Vec<N>> = (0..k).map(|i| coords[i].clone()).collect();
+        let mut assignment = vec![0usize; n];
+        for _ in 0..100 {
+            let mut changed = false;
+            for v in 0..n {
+                let mut best = 0;
+                let mut best_d = euclid(&coords[v], &centroids[0]);
+                for c in 1..k {
+                    let d = euclid(&coords[v], &centroids[c]);
+                    if d < best_d {
+                        best_d = d;
+                        best = c;
+                    }
+                }
+                if assignment[v] != best {
+                    assignment[v] = best;
+                    changed = true;
+                }
+            }
+            let mut sums = vec![vec![N::zero(); dim]; k];
+            let mut counts = vec![0usize; k];
+            for v in 0..n {
+                let c = assignment[v];
+                counts[c] += 1;
+                for d in 0..dim {
+                    sums[c][d] = sums[c][d] + coords[v][d];
+                }
+            }
+            for c in 0..k {
+                if counts[c] > 0 {
+                    for d in 0..dim {
+                        centroids[c][d] = sums[c][d] / N::from(counts[c]).unwrap();
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        // Group the vertices of each non-empty cluster.
+        let mut clusters: Vec<Vec<usize>> = vec![Vec::new(); k];
+        for v in 0..n {
+            clusters[assignment[v]].push(v);
+        }
+        let nonempty: Vec<usize> = (0..k).filter(|&c| !clusters[c].is_empty()).collect();
+        // Order the clusters by a 2-opt tour over their centroids.
+        let order = if nonempty.len() <= 2 {
+            (0..nonempty.len()).collect::<Vec<usize>>()
+        } else {
+            let mut cmatrix = Vec::with_capacity(nonempty.len());
+            for &a in &nonempty {
+                cmatrix.push(
+                    nonempty
+                        .iter()
+                        .map(|&b| euclid(&centroids[a], &centroids[b]))
+                        .collect(),
+                );
+            }
+            let (_, ord) = Self::assemble(cmatrix, None).do_2opt(None);
+            ord
+        };
+        // Solve each cluster's sub-tour and stitch them together in order,
+        // entering each cluster at the vertex cheapest to reach from the last.
+        let mut tour: Vec<usize> = Vec::with_capacity(n);
+        for &oi in &order {
+            let cluster = &clusters[nonempty[oi]];
+            let sub = if cluster.len() <= 3 {
+                cluster.clone()
+            } else {
+                let mut sub_matrix = Vec::with_capacity(cluster.len());
+                for &a in cluster {
+                    sub_matrix.push(cluster.iter().map(|&b| self.distances[a][b]).collect());
+                }
+                let (_, local) = Self::assemble(sub_matrix, None).do_2opt(None);
+                local.iter().map(|&i| cluster[i]).collect()
+            };
+            let m = sub.len();
+            if tour.is_empty() {
+                tour.extend(sub);
+            } else {
+                let last = *tour.last().unwrap();
+                let mut entry = 0;
+                let mut best_d = self.distances[last][sub[0]];
+                for i in 1..m {
+                    let d = self.distances[last][sub[i]];
+                    if d < best_d {
+                        best_d = d;
+                        entry = i;
+                    }
+                }
+                for i in 0..m {
+                    tour.push(sub[(entry + i) % m]);
+                }
+            }
+        }
+        let mut distance = N::zero();
+        for i in 0..tour.len() {
+            distance = distance + self.distances[tour[i]][tour[(i + 1) % tour.len()]];
+        }
+        (distance, tour)
+    }
 }
 
 #[cfg(test)]
@@ -225,6 +776,103 @@ mod tests {
             }
         }
     }
+    #[test]
+    fn exact_square() {
+        let tsp = TSP::<f64>::new_euc2d(&[(0., 0.), (1., 1.), (0., 1.), (1., 0.)]);
+        let (distance, mut path) = tsp.solve_exact();
+        assert!((distance - 4.).abs() < 1e-9);
+        path.sort();
+        assert_eq!(path, vec![0, 1, 2, 3]);
+    }
+    #[test]
+    fn annealing_rectangle() {
+        use std::time::Duration;
+        let tsp = TSP::<f64>::new_euc2d(&[(0., 0.), (1., 1.), (0., 1.), (1., 0.), (2., 0.), (2., 1.)]);
+        let config = super::AnnealConfig {
+            budget: Duration::from_millis(50),
+            t_start: 1.0,
+            t_end: 1e-4,
+            seed: 42,
+        };
+        let (distance, mut path) = tsp.do_annealing(None, config);
+        let (exact, _) = tsp.solve_exact();
+        assert!(distance >= exact - 1e-9);
+        path.sort();
+        assert_eq!(path, vec![0, 1, 2, 3, 4, 5]);
+    }
+    #[test]
+    fn multistart_square() {
+        let tsp = TSP::<f64>::new_euc2d(&[(0., 0.), (1., 1.), (0., 1.), (1., 0.)]);
+        let (distance, mut path) = tsp.do_multistart(8, 1);
+        assert!((distance - 4.).abs() < 1e-9);
+        path.sort();
+        assert_eq!(path, vec![0, 1, 2, 3]);
+    }
+    quickcheck! {
+        fn greedy_is_a_permutation(vs: Vec<(f64, f64)>) -> bool {
+            let tsp = TSP::new_euc2d(&vs);
+            let mut path = tsp.greedy();
+            match path.first() {
+                Some(&0) | None => {}
+                Some(_) => return false,
+            }
+            path.sort();
+            path == (0..vs.len()).collect::<Vec<usize>>()
+        }
+    }
+    #[test]
+    fn nn_square() {
+        let tsp = TSP::<f64>::new_euc2d(&[(0., 0.), (1., 1.), (0., 1.), (1., 0.)]);
+        let (distance, mut path) = tsp.do_2opt_nn(3, None);
+        assert!((distance - 4.).abs() < 1e-9);
+        path.sort();
+        assert_eq!(path, vec![0, 1, 2, 3]);
+    }
+    quickcheck! {
+        fn no_point_missed_2opt_nn(vs: Vec<(f64, f64)>) -> bool {
+            let tsp = TSP::new_euc2d(&vs);
+            let (_, mut path) = tsp.do_2opt_nn(8, None);
+            path.sort();
+            path == (0..vs.len()).collect::<Vec<usize>>()
+        }
+    }
+    #[test]
+    fn clustered_is_a_permutation() {
+        let tsp = TSP::<f64>::new_euc2d(&[
+            (0., 0.), (0., 1.), (1., 0.), (1., 1.),
+            (10., 10.), (10., 11.), (11., 10.), (11., 11.),
+        ]);
+        let (distance, mut tour) = tsp.solve_clustered(2);
+        assert!(distance > 0.);
+        tour.sort();
+        assert_eq!(tour, (0..8).collect::<Vec<usize>>());
+    }
+    #[test]
+    fn from_matrix_triangle() {
+        let tsp = TSP::<f64>::from_matrix(vec![
+            vec![0., 1., 2.],
+            vec![1., 0., 1.],
+            vec![2., 1., 0.],
+        ]);
+        let (distance, _) = tsp.do_2opt(None);
+        assert!((distance - 4.).abs() < 1e-9);
+    }
+    #[test]
+    fn geo_one_degree() {
+        let tsp = TSP::<f64>::new_geo(&[(0., 0.), (0., 1.)]);
+        let (distance, _) = tsp.do_2opt(None);
+        // Two points one degree of longitude apart on the equator, there and
+        // back: about 2 × 111.19 km.
+        assert!((distance - 2. * 111.19).abs() < 1.0);
+    }
+    #[test]
+    fn tsplib_euc2d() {
+        let data = "NAME: test\nTYPE: TSP\nEDGE_WEIGHT_TYPE: EUC_2D\n\
+                    NODE_COORD_SECTION\n1 0.0 0.0\n2 1.0 1.0\n3 0.0 1.0\n4 1.0 0.0\nEOF\n";
+        let tsp = TSP::<f64>::from_tsplib(data.as_bytes()).unwrap();
+        let (distance, _) = tsp.do_2opt(None);
+        assert!((distance - 4.).abs() < 1e-9);
+    }
     quickcheck! {
         fn no_point_missed_3opt(vs: Vec<(f64, f64)>) -> bool {
             let tsp = TSP::new_euc2d(&vs);